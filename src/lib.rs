@@ -0,0 +1,7 @@
+extern crate curv;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate sha2;
+
+pub mod protocols;
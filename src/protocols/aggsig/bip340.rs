@@ -0,0 +1,146 @@
+//! BIP-340 compatible x-only Schnorr signatures
+//!
+//! The plain `verify`/`sign_2` path in [`musig_three_rounds`] hashes
+//! `R.x_coor()` directly and has no notion of even/odd `Y`, so signatures it
+//! produces do not validate against standard secp256k1 Schnorr verifiers.
+//! `State::sign_2_bip340` forces `R` and the group key to even `Y` (by
+//! negating the scalar contribution when the `Y` coordinate is odd) and uses
+//! the BIP-340 tagged hash `H(tag, R.x || P.x || m)` as the challenge;
+//! `verify_bip340` here checks the result the way a standard x-only verifier
+//! would, recovering `R` and `P` from their x-coordinates.
+//!
+//! [`musig_three_rounds`]: super::musig_three_rounds
+use curv::arithmetic::traits::Converter;
+use curv::elliptic::curves::traits::*;
+use curv::{BigInt, FE, GE};
+
+use sha2::{Digest, Sha256};
+
+fn tagged_hash(tag: &str, data: &[&[u8]]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.input(&tag_hash);
+    hasher.input(&tag_hash);
+    for chunk in data {
+        hasher.input(chunk);
+    }
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&hasher.result());
+    out
+}
+
+fn to_32_bytes(n: &BigInt) -> [u8; 32] {
+    let bytes = n.to_vec();
+    let mut out = [0u8; 32];
+    let start = 32 - bytes.len();
+    out[start..].copy_from_slice(&bytes);
+    out
+}
+
+/// `c = SHA256(SHA256("BIP0340/challenge") || SHA256("BIP0340/challenge") || R.x || P.x || m)`
+pub fn challenge(r_x: &BigInt, p_x: &BigInt, message: &[u8]) -> BigInt {
+    let r_x_bytes = to_32_bytes(r_x);
+    let p_x_bytes = to_32_bytes(p_x);
+    let hash = tagged_hash("BIP0340/challenge", &[&r_x_bytes, &p_x_bytes, message]);
+    BigInt::from(&hash[..])
+}
+
+/// +1 if `point` already has an even `Y` coordinate, -1 (mod the group
+/// order) otherwise; multiplying the scalar that generated `point` by this
+/// value forces its public point to even `Y`.
+pub fn even_y_correction(point: &GE) -> FE {
+    let one: FE = ECScalar::from(&BigInt::from(1));
+    let y_bytes = point.y_coor().unwrap().to_vec();
+    let is_even = y_bytes.last().map_or(true, |last_byte| last_byte % 2 == 0);
+    if is_even {
+        one
+    } else {
+        let zero: FE = ECScalar::zero();
+        zero.sub(&one.get_element())
+    }
+}
+
+/// the even-`Y` point with x-coordinate `x` (BIP-340's `lift_x`); `None` if
+/// `x` is not a valid secp256k1 x-coordinate. Reuses `GE`'s own compressed
+/// point parsing rather than computing the modular square root by hand: a
+/// compressed point with a `0x02` prefix byte *is* the even-`Y` root of `x`.
+fn lift_x(x: &BigInt) -> Option<GE> {
+    let mut compressed = vec![0x02u8];
+    compressed.extend_from_slice(&to_32_bytes(x));
+    ECPoint::from_bytes(&compressed).ok()
+}
+
+/// `s*G = R + c*P` for an x-only `(r_x, s)` signature and x-only public key,
+/// lifting both to their even-`Y` points internally, so it validates
+/// signatures produced by `State::sign_2_bip340` without the caller needing
+/// to already know `R`/`P` as points.
+pub fn verify_bip340(sig: &(BigInt, BigInt), pk_xonly: &BigInt, message: &[u8]) -> bool {
+    let (r_x, s) = sig;
+    let (r, p) = match (lift_x(r_x), lift_x(pk_xonly)) {
+        (Some(r), Some(p)) => (r, p),
+        _ => return false,
+    };
+    let c = challenge(r_x, pk_xonly, message);
+    let c_fe: FE = ECScalar::from(&c);
+    let s_fe: FE = ECScalar::from(s);
+    let base_point: GE = ECPoint::generator();
+    let sG = base_point * s_fe;
+    let cP = p * c_fe;
+    sG == r.add_point(&cP.get_element())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use protocols::aggsig::musig_three_rounds::{KeyAgg, KeyPair, State};
+
+    #[test]
+    fn test_single_signer_bip340_round_trip() {
+        let message: [u8; 4] = [79, 77, 69, 82];
+        let key = KeyPair::create();
+        let pk = key.public_key.clone();
+        let pks = vec![pk.clone()];
+
+        let mut party = State::sign_1(key, 2);
+        party.sign_2_bip340(&message, &pks, vec![], 0).unwrap();
+
+        let state1 = party.get_state_1();
+        let sig = (state1.R.x_coor().unwrap(), state1.s_i.to_big_int());
+        assert!(verify_bip340(&sig, &pk.x_coor().unwrap(), &message));
+    }
+
+    #[test]
+    fn test_two_party_bip340_signing_verifies() {
+        let message: [u8; 4] = [79, 77, 69, 82];
+        let nu = 2;
+        let key_1 = KeyPair::create();
+        let key_2 = KeyPair::create();
+        let pks = vec![key_1.public_key.clone(), key_2.public_key.clone()];
+        let key_agg = KeyAgg::key_aggregation_n(&pks, 0);
+
+        let mut party_1 = State::sign_1(key_1, nu);
+        let mut party_2 = State::sign_1(key_2, nu);
+        let party1_first_msg = vec![Vec::from(party_1.get_msg_1())];
+        let party2_first_msg = vec![Vec::from(party_2.get_msg_1())];
+
+        party_1
+            .sign_2_bip340(&message, &pks, party2_first_msg, 0)
+            .unwrap();
+        party_2
+            .sign_2_bip340(&message, &pks, party1_first_msg, 1)
+            .unwrap();
+
+        let s_1 = party_1.get_state_1().s_i;
+        let s_2 = party_2.get_state_1().s_i;
+        let s_total = party_1.sign_3(&vec![s_2]);
+        assert_eq!(s_total, party_2.sign_3(&vec![s_1]));
+
+        let r_x = party_1.get_state_1().R.x_coor().unwrap();
+        let sig = (r_x, s_total.to_big_int());
+        assert!(verify_bip340(
+            &sig,
+            &key_agg.apk.x_coor().unwrap(),
+            &message
+        ));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod bip340;
+pub mod musig_three_rounds;
+pub mod round_based;
+pub mod serialize;
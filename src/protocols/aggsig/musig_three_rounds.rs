@@ -13,7 +13,20 @@ use curv::arithmetic::traits::Converter;
 use curv::cryptographic_primitives::commitments::hash_commitment::HashCommitment;
 use curv::cryptographic_primitives::commitments::traits::*;
 
-const NUM_OF_SHARES: usize = 2;
+use protocols::aggsig::bip340;
+use protocols::aggsig::serialize::{bigint, fe, fe_opt, ge, vec_bigint, vec_ge};
+
+/// default number of MuSig2 nonces (\nu) per signer when a caller does not
+/// need extra concurrency-attack resistance
+pub const NUM_OF_SHARES: usize = 2;
+
+/// returned when a signer's nonce-commitment broadcast doesn't have the
+/// same \nu as the local party, instead of panicking on an out-of-range index
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NonceCountMismatch {
+    pub expected: usize,
+    pub got: usize,
+}
 
 #[derive(Debug)]
 pub struct KeyPair {
@@ -43,9 +56,11 @@ impl KeyPair {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct KeyAgg {
+    #[serde(with = "ge")]
     pub apk: GE,
+    #[serde(with = "bigint")]
     pub hash: BigInt,
 }
 
@@ -123,9 +138,12 @@ pub struct EphemeralKey {
 }
 
 impl EphemeralKey {
-    pub fn create_vec_from_private_key(x1: &KeyPair) -> Vec<EphemeralKey> {
+    /// generates \nu ephemeral (nonce) keys for the MuSig2 nonce-aggregation
+    /// scheme. \nu = 2 is the standard choice; a caller wanting stronger
+    /// concurrency-attack resistance may pass a larger \nu.
+    pub fn create_vec_from_private_key(x1: &KeyPair, nu: usize) -> Vec<EphemeralKey> {
         let mut EphermalKeys_vec: Vec<EphemeralKey> = vec![];
-        for i in 0..NUM_OF_SHARES {
+        for i in 0..nu {
             let base_point: GE = ECPoint::generator();
             let hash_private_key_message =
                 HSha256::create_hash(&[&x1.private_key.to_big_int(), &BigInt::from(i as i32)]);
@@ -157,8 +175,21 @@ impl EphemeralKey {
     }
 }
 
+/// a final, combined Schnorr signature in wire form: `(r_x, s)` where
+/// `r_x` is the aggregated nonce's x-coordinate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Signature {
+    #[serde(with = "bigint")]
+    pub r_x: BigInt,
+    #[serde(with = "bigint")]
+    pub s: BigInt,
+}
+
+#[derive(Serialize, Deserialize)]
 pub struct Msg {
+    #[serde(with = "vec_ge")]
     first_msg: Vec<GE>,
+    #[serde(with = "fe_opt")]
     second_msg: Option<FE>,
 }
 
@@ -166,12 +197,17 @@ pub struct State0 {
     pub keypair: KeyPair,
     pub ephk_vec: Vec<EphemeralKey>,
 }
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct State1 {
+    #[serde(with = "ge")]
     pub R: GE,
+    #[serde(with = "fe")]
     pub s_i: FE,
+    #[serde(with = "bigint")]
     pub c: BigInt,
+    #[serde(with = "fe")]
     pub r_i: FE,
+    #[serde(with = "vec_bigint")]
     pub b_coefficients: Vec<BigInt>,
 }
 
@@ -234,26 +270,34 @@ impl State {
         //Doron: sk,r,a,c->r+c*a*sk
     }
 
-    pub fn add_ephemeral_keys(&mut self, msg_vec: &[Vec<GE>], party_index: usize) -> Vec<GE> {
-        let mut R_j_vec: Vec<GE> = vec![];
-        //println!("msg_vec {:?}",msg_vec);
+    pub fn add_ephemeral_keys(
+        &mut self,
+        msg_vec: &[Vec<GE>],
+        party_index: usize,
+    ) -> Result<Vec<GE>, NonceCountMismatch> {
+        let nu = self.State0.ephk_vec.len();
+        for msg in msg_vec {
+            if msg.len() != nu {
+                return Err(NonceCountMismatch {
+                    expected: nu,
+                    got: msg.len(),
+                });
+            }
+        }
 
-        for j in 0..NUM_OF_SHARES {
+        let mut R_j_vec: Vec<GE> = vec![];
+        for j in 0..nu {
             let pk_0j = self.State0.ephk_vec[j].keypair.public_key;
-            //    println!("self_vec {:?}",pk_0j);
-
-            let R_j: GE = msg_vec.
-                iter().
-                //  map(|emph| emph.first_msg.get(j)).
-                fold(pk_0j, |acc, ephk| acc.add_point(&ephk.get(j).unwrap().get_element()));
+            let R_j: GE = msg_vec
+                .iter()
+                .fold(pk_0j, |acc, ephk| acc.add_point(&ephk[j].get_element()));
             R_j_vec.push(R_j);
         }
-        //  println!("R_j_vec {:?}",R_j_vec);
-        R_j_vec
+        Ok(R_j_vec)
     }
 
-    pub fn sign_1(x: KeyPair) -> State {
-        let ephk_vec = EphemeralKey::create_vec_from_private_key(&x);
+    pub fn sign_1(x: KeyPair, nu: usize) -> State {
+        let ephk_vec = EphemeralKey::create_vec_from_private_key(&x, nu);
         let msg = ephk_vec
             .iter()
             .map(|eph_key| eph_key.keypair.public_key)
@@ -289,48 +333,87 @@ impl State {
         pks: &Vec<GE>,
         msg_vec: Vec<Vec<GE>>,
         party_index: usize,
-    ) -> (GE, GE) {
+    ) -> Result<(GE, GE), NonceCountMismatch> {
         let key_agg = KeyAgg::key_aggregation_n(&pks, party_index);
-        let mut R_j_vec = self.add_ephemeral_keys(&msg_vec, party_index);
-        println!("R_j_vec: {:?}", R_j_vec);
-        let mut b_coefficients: Vec<BigInt> = Vec::new();
-        b_coefficients.push(BigInt::from(1));
+        let R_j_vec = self.add_ephemeral_keys(&msg_vec, party_index)?;
+        let (R, b_coefficients) = State::aggregate_nonces(&key_agg.apk, R_j_vec, message);
+        let c = State::hash_0(&R, &key_agg.apk, message, true);
 
-        for j in 1..NUM_OF_SHARES {
+        let (s_i, r_i) = self.sign_0(&b_coefficients, &c, &self.State0.keypair, &key_agg.hash);
+        let base_point: GE = ECPoint::generator();
+        let left_arg: GE = base_point * s_i;
+        let pub_key = self.State0.keypair.public_key;
+        let a_i: FE = ECScalar::from(&key_agg.hash);
+        let c_fe: FE = ECScalar::from(&c);
+        let right_arg: GE = pub_key * a_i * c_fe + base_point * r_i;
+        // assert_eq!(left_arg,right_arg);
+        self.State1 = Some(State1 {
+            R,
+            s_i,
+            c,
+            r_i,
+            b_coefficients,
+        });
+        //    self
+        Ok((left_arg, right_arg))
+    }
+
+    pub fn sign_3(&self, msg_vec: &Vec<FE>) -> FE {
+        let s_0 = self.State1.as_ref().unwrap().s_i;
+        msg_vec.iter().fold(s_0, |acc, s_i| acc + s_i)
+    }
+
+    /// MuSig2 nonce aggregation shared by `sign_2`, `sign_2_threshold` and
+    /// `sign_2_bip340`: combines each signer's per-nonce broadcast `R_j_vec`
+    /// into a single aggregated `R = sum_j b_j * R_j`, where `b_1` is fixed
+    /// at 1 and `b_2..b_nu = H(apk, R_1..R_nu, m, j)`
+    fn aggregate_nonces(apk: &GE, mut R_j_vec: Vec<GE>, message: &[u8]) -> (GE, Vec<BigInt>) {
+        let nu = R_j_vec.len();
+        let mut b_coefficients: Vec<BigInt> = vec![BigInt::from(1)];
+
+        for j in 1..nu {
             let mut hnon_preimage: Vec<BigInt> = Vec::new();
-            hnon_preimage.push(key_agg.apk.bytes_compressed_to_big_int());
-            for i in 0..NUM_OF_SHARES {
+            hnon_preimage.push(apk.bytes_compressed_to_big_int());
+            for i in 0..nu {
                 hnon_preimage.push(R_j_vec[i].bytes_compressed_to_big_int());
             }
             hnon_preimage.push(BigInt::from(message));
             hnon_preimage.push(BigInt::from(j as i32));
-            //   let b_j = HSha256::create_hash(&hnon_preimage.iter().collect::<Vec<_>>());
-            let b_j = HSha256::create_hash(&hnon_preimage.iter().collect::<Vec<_>>());
-            b_coefficients.push(b_j);
-            //            R = R.add_point(R_j_vec[j].scalar_mul(b_j));
+            b_coefficients.push(HSha256::create_hash(&hnon_preimage.iter().collect::<Vec<_>>()));
         }
         let R_j0 = R_j_vec.remove(0);
         let mut b_coefficients_temp = b_coefficients.clone();
         let b_0 = b_coefficients_temp.remove(0);
         let R_0 = R_j0 * &<FE as ECScalar<_>>::from(&b_0);
-        //   .scalar_mul(b_coefficients.remove(0));
         let R: GE = R_j_vec
             .iter()
             .zip(b_coefficients_temp.clone())
             .map(|(R_j, b_j)| R_j * &<FE as ECScalar<_>>::from(&b_j))
             .fold(R_0, |acc, R_j| acc.add_point(&R_j.get_element()));
-        let c = State::hash_0(&R, &key_agg.apk, message, true);
+        (R, b_coefficients)
+    }
 
-        let (s_i, r_i) = self.sign_0(&b_coefficients, &c, &self.State0.keypair, &key_agg.hash);
+    /// like `sign_2`, but for a DKG-generated `apk` and a `lambda_j` from
+    /// `lagrange_coefficient` in place of the naive `KeyAgg` coefficient
+    pub fn sign_2_threshold(
+        &mut self,
+        message: &[u8],
+        apk: &GE,
+        msg_vec: Vec<Vec<GE>>,
+        party_index: usize,
+        lambda_j: &BigInt,
+    ) -> Result<(GE, GE), NonceCountMismatch> {
+        let R_j_vec = self.add_ephemeral_keys(&msg_vec, party_index)?;
+        let (R, b_coefficients) = State::aggregate_nonces(apk, R_j_vec, message);
+        let c = State::hash_0(&R, apk, message, true);
+
+        let (s_i, r_i) = self.sign_0(&b_coefficients, &c, &self.State0.keypair, lambda_j);
         let base_point: GE = ECPoint::generator();
         let left_arg: GE = base_point * s_i;
         let pub_key = self.State0.keypair.public_key;
-        let a_i: FE = ECScalar::from(&key_agg.hash);
+        let lambda_fe: FE = ECScalar::from(lambda_j);
         let c_fe: FE = ECScalar::from(&c);
-        let right_arg: GE = pub_key * a_i * c_fe + base_point * r_i;
-        // assert_eq!(left_arg,right_arg);
-        println!("left_arg = {:?}", left_arg.get_element());
-        println!("right_arg = {:?}", right_arg.get_element());
+        let right_arg: GE = pub_key * lambda_fe * c_fe + base_point * r_i;
         self.State1 = Some(State1 {
             R,
             s_i,
@@ -338,14 +421,46 @@ impl State {
             r_i,
             b_coefficients,
         });
-        println!("state1 {:?}", self.State1);
-        //    self
-        (left_arg, right_arg)
+        Ok((left_arg, right_arg))
     }
 
-    pub fn sign_3(&self, msg_vec: &Vec<FE>) -> FE {
-        let s_0 = self.State1.as_ref().unwrap().s_i;
-        msg_vec.iter().fold(s_0, |acc, s_i| acc + s_i)
+    /// like `sign_2`, but derives the challenge from `bip340::challenge` and
+    /// forces the aggregated nonce `R` and group key to even `Y` (per
+    /// `bip340::even_y_correction`) before computing `s_i`, so the resulting
+    /// partial signatures sum to a signature that validates against
+    /// `bip340::verify_bip340`
+    pub fn sign_2_bip340(
+        &mut self,
+        message: &[u8],
+        pks: &Vec<GE>,
+        msg_vec: Vec<Vec<GE>>,
+        party_index: usize,
+    ) -> Result<(), NonceCountMismatch> {
+        let key_agg = KeyAgg::key_aggregation_n(&pks, party_index);
+        let R_j_vec = self.add_ephemeral_keys(&msg_vec, party_index)?;
+        let (R, b_coefficients) = State::aggregate_nonces(&key_agg.apk, R_j_vec, message);
+
+        let c = bip340::challenge(&R.x_coor().unwrap(), &key_agg.apk.x_coor().unwrap(), message);
+        let r_sign = bip340::even_y_correction(&R);
+        let p_sign = bip340::even_y_correction(&key_agg.apk);
+
+        let lin_comb_ephemeral_i: FE = self.State0.ephk_vec.iter().zip(&b_coefficients).fold(
+            ECScalar::zero(),
+            |acc, (ephk, b)| acc + ephk.keypair.private_key * <FE as ECScalar<_>>::from(b),
+        );
+        let a_i: FE = ECScalar::from(&key_agg.hash);
+        let c_fe: FE = ECScalar::from(&c);
+        let r_i = lin_comb_ephemeral_i * r_sign;
+        let s_i = r_i + (a_i * p_sign * self.State0.keypair.private_key * c_fe);
+
+        self.State1 = Some(State1 {
+            R,
+            s_i,
+            c,
+            r_i,
+            b_coefficients,
+        });
+        Ok(())
     }
 
     pub fn add_signature_parts(s1: BigInt, s2: &BigInt, r_tag: &GE) -> (BigInt, BigInt) {
@@ -380,6 +495,38 @@ pub fn verify_partial(
     }
 }
 
+/// \lambda_j = \prod_{m \in quorum, m \neq j} m (m - j)^{-1} mod q, for
+/// participant `j` within the active signer set `quorum` (1-indexed point
+/// labels, matching the labels `dkg`'s `VerifiableSS` shares over).
+pub fn lagrange_coefficient(j: usize, quorum: &[usize]) -> BigInt {
+    let j_fe: FE = ECScalar::from(&BigInt::from(j as u64));
+    let lambda: FE = quorum.iter().filter(|&&m| m != j).fold(
+        ECScalar::from(&BigInt::from(1)),
+        |acc: FE, &m| {
+            let m_fe: FE = ECScalar::from(&BigInt::from(m as u64));
+            let m_minus_j_inv = m_fe.sub(&j_fe.get_element()).invert();
+            acc * m_fe * m_minus_j_inv
+        },
+    );
+    lambda.to_big_int()
+}
+
+/// verifies signer `j`'s partial signature against the shared group key,
+/// deriving \lambda_j from `quorum` itself rather than trusting a
+/// caller-supplied coefficient, so a coordinator can't be tricked into
+/// blaming (or clearing) a signer with a \lambda_j for the wrong quorum.
+pub fn verify_partial_threshold(
+    signature: &FE,
+    r_x: &BigInt,
+    c: &FE,
+    j: usize,
+    quorum: &[usize],
+    key_pub: &GE,
+) -> Result<(), ProofError> {
+    let lambda_j: FE = ECScalar::from(&lagrange_coefficient(j, quorum));
+    verify_partial(signature, r_x, c, &lambda_j, key_pub)
+}
+
 
 pub fn verify(
     signature: &FE,
@@ -407,21 +554,25 @@ mod tests {
     use protocols::aggsig::musig_three_rounds::*;
 
     extern crate hex;
+    extern crate serde_json;
 
+    use curv::cryptographic_primitives::hashing::hash_sha256::HSha256;
+    use curv::cryptographic_primitives::hashing::traits::*;
     use curv::elliptic::curves::traits::*;
 
     #[test]
     fn test_multiparty_signing_for_two_parties() {
         let is_musig = true;
         let message: [u8; 4] = [79, 77, 69, 82];
+        let nu = NUM_OF_SHARES;
 
         // round 0: generate signing keys
         let party1_key = KeyPair::create();
         let party2_key = KeyPair::create();
 
         // round 1: send commitments to ephemeral public keys
-        let party1_ephemeral_keys = EphemeralKey::create_vec_from_private_key(&party1_key);
-        let party2_ephemeral_keys = EphemeralKey::create_vec_from_private_key(&party2_key);
+        let party1_ephemeral_keys = EphemeralKey::create_vec_from_private_key(&party1_key, nu);
+        let party2_ephemeral_keys = EphemeralKey::create_vec_from_private_key(&party2_key, nu);
         let mut vec_r_1 = vec![
             party1_ephemeral_keys[0].keypair.public_key,
             party1_ephemeral_keys[1].keypair.public_key,
@@ -441,20 +592,20 @@ mod tests {
         // compute R' = R1+R2:
 
         assert_eq!(party1_key_agg.apk, party2_key_agg.apk);
-        let mut party_1 = State::sign_1(party1_key);
-        let mut party_2 = State::sign_1(party2_key);
+        let mut party_1 = State::sign_1(party1_key, nu);
+        let mut party_2 = State::sign_1(party2_key, nu);
 
         let party1_first_msg = vec![Vec::from(party_1.get_msg_1())];
         let party2_first_msg = vec![Vec::from(party_2.get_msg_1())];
 
-        let R1_vec: Vec<GE> = party_1.add_ephemeral_keys(&party2_first_msg, 0);
-        let R2_vec: Vec<GE> = party_2.add_ephemeral_keys(&party1_first_msg, 1);
+        let R1_vec: Vec<GE> = party_1.add_ephemeral_keys(&party2_first_msg, 0).unwrap();
+        let R2_vec: Vec<GE> = party_2.add_ephemeral_keys(&party1_first_msg, 1).unwrap();
 
         assert_eq!(R1_vec, R2_vec);
         let (left_arg_partial, right_arg_partial) =
-            party_1.sign_2(&message, &pks, party2_first_msg, 0);
+            party_1.sign_2(&message, &pks, party2_first_msg, 0).unwrap();
         let (left_arg_partial, right_arg_partial) =
-            party_2.sign_2(&message, &pks, party1_first_msg, 1);
+            party_2.sign_2(&message, &pks, party1_first_msg, 1).unwrap();
         let base_point: GE = ECPoint::generator();
 
         let r_1: FE = party_1.get_state_1().r_i;
@@ -491,6 +642,186 @@ mod tests {
 
 
     }
+
+    #[test]
+    fn test_r_j_vec_serde_roundtrip_reproduces_same_aggregate() {
+        let nu = NUM_OF_SHARES;
+        let message: [u8; 4] = [79, 77, 69, 82];
+
+        let party1_key = KeyPair::create();
+        let party2_key = KeyPair::create();
+        let mut pks: Vec<GE> = Vec::new();
+        pks.push(party1_key.public_key.clone());
+        pks.push(party2_key.public_key.clone());
+        let key_agg = KeyAgg::key_aggregation_n(&pks, 0);
+
+        let mut party_1 = State::sign_1(party1_key, nu);
+        let party_2 = State::sign_1(party2_key, nu);
+        let party1_first_msg = vec![Vec::from(party_1.get_msg_1())];
+        let party2_first_msg = vec![Vec::from(party_2.get_msg_1())];
+
+        let r_j_vec = party_1.add_ephemeral_keys(&party2_first_msg, 0).unwrap();
+
+        // wrap the nonce-commitment broadcast the way it goes over the
+        // wire, round-trip it through JSON, and check the round-tripped
+        // copy still reproduces the same aggregated R and challenge c
+        let wire = Msg {
+            first_msg: r_j_vec.clone(),
+            second_msg: None,
+        };
+        let json = serde_json::to_string(&wire).unwrap();
+        let wire_deserialized: Msg = serde_json::from_str(&json).unwrap();
+        assert_eq!(wire_deserialized.first_msg, r_j_vec);
+
+        let aggregate = |r_j_vec: &[GE]| -> (GE, BigInt) {
+            let mut b_coefficients: Vec<BigInt> = vec![BigInt::from(1)];
+            for j in 1..nu {
+                let mut hnon_preimage: Vec<BigInt> = Vec::new();
+                hnon_preimage.push(key_agg.apk.bytes_compressed_to_big_int());
+                for i in 0..nu {
+                    hnon_preimage.push(r_j_vec[i].bytes_compressed_to_big_int());
+                }
+                hnon_preimage.push(BigInt::from(&message[..]));
+                hnon_preimage.push(BigInt::from(j as i32));
+                let b_j = HSha256::create_hash(&hnon_preimage.iter().collect::<Vec<_>>());
+                b_coefficients.push(b_j);
+            }
+            let r_0: GE = r_j_vec[0] * &<FE as ECScalar<_>>::from(&b_coefficients[0]);
+            let r: GE = r_j_vec[1..]
+                .iter()
+                .zip(b_coefficients[1..].to_vec())
+                .map(|(r_j, b_j)| r_j * &<FE as ECScalar<_>>::from(&b_j))
+                .fold(r_0, |acc, r_j| acc.add_point(&r_j.get_element()));
+            let c = State::hash_0(&r, &key_agg.apk, &message, true);
+            (r, c)
+        };
+
+        let (r_before, c_before) = aggregate(&r_j_vec);
+        let (r_after, c_after) = aggregate(&wire_deserialized.first_msg);
+        assert_eq!(r_before, r_after);
+        assert_eq!(c_before, c_after);
+    }
+
+    #[test]
+    fn test_signature_serde_roundtrip() {
+        let signature = Signature {
+            r_x: BigInt::from(12345),
+            s: BigInt::from(67890),
+        };
+        let json = serde_json::to_string(&signature).unwrap();
+        let deserialized: Signature = serde_json::from_str(&json).unwrap();
+        assert_eq!(signature, deserialized);
+    }
+
+    #[test]
+    fn test_multiparty_signing_with_larger_nu() {
+        let message: [u8; 4] = [79, 77, 69, 82];
+        let nu = 3;
+
+        let party1_key = KeyPair::create();
+        let party2_key = KeyPair::create();
+        let mut pks: Vec<GE> = Vec::new();
+        pks.push(party1_key.public_key.clone());
+        pks.push(party2_key.public_key.clone());
+        let party1_key_agg = KeyAgg::key_aggregation_n(&pks, 0);
+
+        let mut party_1 = State::sign_1(party1_key, nu);
+        let mut party_2 = State::sign_1(party2_key, nu);
+        let party1_first_msg = vec![Vec::from(party_1.get_msg_1())];
+        let party2_first_msg = vec![Vec::from(party_2.get_msg_1())];
+
+        let (_, _) = party_1
+            .sign_2(&message, &pks, party2_first_msg, 0)
+            .unwrap();
+        let (_, _) = party_2
+            .sign_2(&message, &pks, party1_first_msg, 1)
+            .unwrap();
+
+        let s_1 = party_1.get_state_1().s_i;
+        let s_2 = party_2.get_state_1().s_i;
+        let R1 = party_1.get_state_1().R;
+        let s_total = party_1.sign_3(&vec![s_2]);
+        assert_eq!(s_total, party_2.sign_3(&vec![s_1]));
+        assert!(verify(&s_total, &R1.x_coor().unwrap(), &party1_key_agg.apk, &party_1.get_state_1().c).is_ok());
+    }
+
+    #[test]
+    fn test_mismatched_nu_is_reported_not_panicked() {
+        let party1_key = KeyPair::create();
+        let party2_key = KeyPair::create();
+        let mut party_1 = State::sign_1(party1_key, 2);
+        let party_2 = State::sign_1(party2_key, 3);
+
+        let party2_first_msg = vec![Vec::from(party_2.get_msg_1())];
+        let err = party_1
+            .add_ephemeral_keys(&party2_first_msg, 0)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            NonceCountMismatch {
+                expected: 2,
+                got: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_threshold_signing_two_of_two() {
+        let base_point: GE = ECPoint::generator();
+        let nu = NUM_OF_SHARES;
+        let message: [u8; 4] = [79, 77, 69, 82];
+
+        // a degree-1 polynomial f(x) = a0 + a1*x; apk = g^{f(0)} and each
+        // party holds its share f(1), f(2)
+        let a0: FE = ECScalar::new_random();
+        let a1: FE = ECScalar::new_random();
+        let one: FE = ECScalar::from(&BigInt::from(1));
+        let two: FE = ECScalar::from(&BigInt::from(2));
+        let x_1 = a0 + a1 * one;
+        let x_2 = a0 + a1 * two;
+        let apk = base_point * a0;
+        let quorum = vec![1usize, 2usize];
+
+        let key_1 = KeyPair::create_from_private_key(&x_1.to_big_int());
+        let key_2 = KeyPair::create_from_private_key(&x_2.to_big_int());
+        let pk_1 = key_1.public_key;
+        let lambda_1 = lagrange_coefficient(1, &quorum);
+        let lambda_2 = lagrange_coefficient(2, &quorum);
+
+        let mut party_1 = State::sign_1(key_1, nu);
+        let mut party_2 = State::sign_1(key_2, nu);
+        let party1_first_msg = vec![Vec::from(party_1.get_msg_1())];
+        let party2_first_msg = vec![Vec::from(party_2.get_msg_1())];
+
+        party_1
+            .sign_2_threshold(&message, &apk, party2_first_msg, 0, &lambda_1)
+            .unwrap();
+        party_2
+            .sign_2_threshold(&message, &apk, party1_first_msg, 1, &lambda_2)
+            .unwrap();
+
+        let s_1 = party_1.get_state_1().s_i;
+        let s_2 = party_2.get_state_1().s_i;
+        let r_1 = party_1.get_state_1().r_i;
+        let c = party_1.get_state_1().c.clone();
+        let R1 = party_1.get_state_1().R;
+        assert_eq!(R1, party_2.get_state_1().R);
+
+        let r_1_point: GE = base_point * r_1;
+        assert!(verify_partial_threshold(
+            &s_1,
+            &r_1_point.x_coor().unwrap(),
+            &ECScalar::from(&c),
+            1,
+            &quorum,
+            &pk_1,
+        )
+        .is_ok());
+
+        let s_total = party_1.sign_3(&vec![s_2]);
+        assert_eq!(s_total, party_2.sign_3(&vec![s_1]));
+        assert!(verify(&s_total, &R1.x_coor().unwrap(), &apk, &c).is_ok());
+    }
 }
 /*
 
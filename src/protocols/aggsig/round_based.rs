@@ -0,0 +1,231 @@
+//! round-based signing state machine
+//!
+//! Wraps the ad-hoc `sign_1`/`sign_2`/`sign_3` flow of [`State`] in an
+//! explicit sequence of typed rounds: `Round1` emits the nonce-commitment
+//! broadcast, `Round2` emits the partial signature, `Round3` emits the
+//! aggregate. Each round's `proceed` validates it received exactly the
+//! expected n-1 broadcasts and returns a [`RoundError`] instead of panicking.
+use std::collections::BTreeMap;
+
+use curv::{FE, GE};
+
+use protocols::aggsig::musig_three_rounds::{KeyPair, NonceCountMismatch, State};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RoundError {
+    /// `proceed` was called with a message from a party index that is not
+    /// part of this signing session, or that echoes this party's own index
+    UnexpectedParty { party_index: usize },
+    /// `proceed` was called before every other party's message for this
+    /// round had arrived
+    MissingMessages { missing: Vec<usize> },
+    /// a party's broadcast didn't have this session's \nu
+    NonceCountMismatch { expected: usize, got: usize },
+}
+
+impl From<NonceCountMismatch> for RoundError {
+    fn from(e: NonceCountMismatch) -> RoundError {
+        RoundError::NonceCountMismatch {
+            expected: e.expected,
+            got: e.got,
+        }
+    }
+}
+
+fn missing_parties(parties: &[usize], party_index: usize, present: &[usize]) -> Vec<usize> {
+    parties
+        .iter()
+        .cloned()
+        .filter(|p| *p != party_index && !present.contains(p))
+        .collect()
+}
+
+/// a party index in `present` that isn't a member of this session, or that
+/// is this party's own index (no party should be sending itself a message)
+fn unexpected_party(parties: &[usize], party_index: usize, present: &[usize]) -> Option<usize> {
+    present
+        .iter()
+        .cloned()
+        .find(|p| *p == party_index || !parties.contains(p))
+}
+
+/// round 1: broadcasts this party's vector of ephemeral nonce public keys
+pub struct Round1 {
+    party_index: usize,
+    parties: Vec<usize>,
+    state: State,
+}
+
+impl Round1 {
+    pub fn new(party_index: usize, parties: Vec<usize>, keypair: KeyPair, nu: usize) -> (Round1, Vec<GE>) {
+        let state = State::sign_1(keypair, nu);
+        let first_msg = state.get_msg_1().to_vec();
+        (
+            Round1 {
+                party_index,
+                parties,
+                state,
+            },
+            first_msg,
+        )
+    }
+
+    /// consumes every other party's round-1 broadcast, indexed by party,
+    /// and produces round 2 together with this party's aggregated
+    /// per-nonce public keys R_j
+    pub fn proceed(
+        mut self,
+        incoming: BTreeMap<usize, Vec<GE>>,
+    ) -> Result<(Round2, Vec<GE>), RoundError> {
+        let present: Vec<usize> = incoming.keys().cloned().collect();
+        if let Some(party_index) = unexpected_party(&self.parties, self.party_index, &present) {
+            return Err(RoundError::UnexpectedParty { party_index });
+        }
+        let missing = missing_parties(&self.parties, self.party_index, &present);
+        if !missing.is_empty() {
+            return Err(RoundError::MissingMessages { missing });
+        }
+        let msg_vec: Vec<Vec<GE>> = incoming.into_iter().map(|(_, v)| v).collect();
+        let r_j_vec = self.state.add_ephemeral_keys(&msg_vec, self.party_index)?;
+        Ok((
+            Round2 {
+                party_index: self.party_index,
+                parties: self.parties,
+                state: self.state,
+                msg_vec,
+            },
+            r_j_vec,
+        ))
+    }
+}
+
+/// round 2: consumes every other party's nonce-commitment broadcast and the
+/// full set of signers' public keys, and emits this party's partial
+/// signature
+pub struct Round2 {
+    party_index: usize,
+    parties: Vec<usize>,
+    state: State,
+    msg_vec: Vec<Vec<GE>>,
+}
+
+impl Round2 {
+    pub fn proceed(mut self, message: &[u8], pks: &Vec<GE>) -> Result<(Round3, FE), RoundError> {
+        self.state
+            .sign_2(message, pks, self.msg_vec, self.party_index)?;
+        let s_i = self.state.get_state_1().s_i;
+        Ok((
+            Round3 {
+                party_index: self.party_index,
+                parties: self.parties,
+                state: self.state,
+            },
+            s_i,
+        ))
+    }
+}
+
+/// round 3: consumes every other party's partial signature and produces the
+/// aggregate signature share
+pub struct Round3 {
+    party_index: usize,
+    parties: Vec<usize>,
+    state: State,
+}
+
+impl Round3 {
+    pub fn proceed(self, incoming: BTreeMap<usize, FE>) -> Result<FE, RoundError> {
+        let present: Vec<usize> = incoming.keys().cloned().collect();
+        if let Some(party_index) = unexpected_party(&self.parties, self.party_index, &present) {
+            return Err(RoundError::UnexpectedParty { party_index });
+        }
+        let missing = missing_parties(&self.parties, self.party_index, &present);
+        if !missing.is_empty() {
+            return Err(RoundError::MissingMessages { missing });
+        }
+        let others: Vec<FE> = incoming.into_iter().map(|(_, v)| v).collect();
+        Ok(self.state.sign_3(&others))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curv::elliptic::curves::traits::*;
+    use protocols::aggsig::musig_three_rounds::{verify, KeyAgg, KeyPair};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_two_party_round_trip_produces_valid_signature() {
+        let message: [u8; 4] = [79, 77, 69, 82];
+        let parties = vec![0, 1];
+        let nu = 2;
+
+        let key_1 = KeyPair::create();
+        let key_2 = KeyPair::create();
+        let pks = vec![key_1.public_key.clone(), key_2.public_key.clone()];
+        let key_agg = KeyAgg::key_aggregation_n(&pks, 0);
+
+        let (round1_1, msg1_1) = Round1::new(0, parties.clone(), key_1, nu);
+        let (round1_2, msg1_2) = Round1::new(1, parties.clone(), key_2, nu);
+
+        let mut incoming_1: BTreeMap<usize, Vec<_>> = BTreeMap::new();
+        incoming_1.insert(1, msg1_2);
+        let mut incoming_2: BTreeMap<usize, Vec<_>> = BTreeMap::new();
+        incoming_2.insert(0, msg1_1);
+
+        let (round2_1, _) = round1_1.proceed(incoming_1).unwrap();
+        let (round2_2, _) = round1_2.proceed(incoming_2).unwrap();
+
+        let (round3_1, s_1) = round2_1.proceed(&message, &pks).unwrap();
+        let (round3_2, s_2) = round2_2.proceed(&message, &pks).unwrap();
+
+        let r_x = round3_1.state.get_state_1().R.x_coor().unwrap();
+        let c = round3_1.state.get_state_1().c.clone();
+
+        let mut incoming_3_1: BTreeMap<usize, _> = BTreeMap::new();
+        incoming_3_1.insert(1, s_2);
+        let mut incoming_3_2: BTreeMap<usize, _> = BTreeMap::new();
+        incoming_3_2.insert(0, s_1);
+
+        let s_total_1 = round3_1.proceed(incoming_3_1).unwrap();
+        let s_total_2 = round3_2.proceed(incoming_3_2).unwrap();
+        assert_eq!(s_total_1, s_total_2);
+        assert!(verify(&s_total_1, &r_x, &key_agg.apk, &c).is_ok());
+    }
+
+    #[test]
+    fn test_round1_missing_message_is_reported() {
+        let parties = vec![0, 1, 2];
+        let (round1, _) = Round1::new(0, parties, KeyPair::create(), 2);
+        let incoming = BTreeMap::new();
+        let err = round1.proceed(incoming).unwrap_err();
+        assert_eq!(
+            err,
+            RoundError::MissingMessages {
+                missing: vec![1, 2]
+            }
+        );
+    }
+
+    #[test]
+    fn test_round1_rejects_message_from_own_party_index() {
+        let parties = vec![0, 1];
+        let (round1_0, msg_0) = Round1::new(0, parties.clone(), KeyPair::create(), 2);
+        let mut incoming: BTreeMap<usize, Vec<_>> = BTreeMap::new();
+        incoming.insert(0, msg_0);
+        let err = round1_0.proceed(incoming).unwrap_err();
+        assert_eq!(err, RoundError::UnexpectedParty { party_index: 0 });
+    }
+
+    #[test]
+    fn test_round1_rejects_message_from_party_outside_session() {
+        let parties = vec![0, 1];
+        let (round1_0, _) = Round1::new(0, parties.clone(), KeyPair::create(), 2);
+        let (_, msg_7) = Round1::new(7, vec![0, 7], KeyPair::create(), 2);
+        let mut incoming: BTreeMap<usize, Vec<_>> = BTreeMap::new();
+        incoming.insert(7, msg_7);
+        let err = round1_0.proceed(incoming).unwrap_err();
+        assert_eq!(err, RoundError::UnexpectedParty { party_index: 7 });
+    }
+}
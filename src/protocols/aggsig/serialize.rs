@@ -0,0 +1,191 @@
+//! `serde` support for curv's elliptic-curve types
+//!
+//! `GE`, `FE` and `BigInt` are foreign types, so they cannot derive
+//! `Serialize`/`Deserialize` directly; instead each submodule here is meant
+//! to be used with `#[serde(with = "...")]` on the field that holds it.
+//! Points are serialized in compressed form, scalars as a fixed 32-byte
+//! big-endian encoding (so every `FE` round-trips to the same byte length
+//! regardless of its numeric value), and arbitrary-size `BigInt`s as a
+//! variable-length big-endian hex string.
+use curv::elliptic::curves::traits::*;
+use curv::{BigInt, FE, GE};
+
+use curv::arithmetic::traits::Converter;
+use serde::de::{Error, Visitor};
+use serde::{Deserializer, Serializer};
+use std::fmt;
+
+/// number of bytes in a fixed-width scalar (secp256k1's order is <2^256)
+const FE_BYTE_LEN: usize = 32;
+
+fn bigint_to_fixed_hex(n: &BigInt, byte_len: usize) -> String {
+    let hex = n.to_hex();
+    let hex = if hex.len() % 2 == 1 {
+        format!("0{}", hex)
+    } else {
+        hex
+    };
+    format!("{:0>width$}", hex, width = byte_len * 2)
+}
+
+/// compressed-point serialization for a single `GE`
+pub mod ge {
+    use super::*;
+
+    pub fn serialize<S>(point: &GE, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&point.bytes_compressed_to_big_int().to_hex())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<GE, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hex = deserializer.deserialize_str(HexVisitor)?;
+        let n = BigInt::from_hex(&hex);
+        Ok(ECPoint::from_bytes(&BigInt::to_vec(&n)).map_err(|_| Error::custom("invalid point"))?)
+    }
+}
+
+/// compressed-point serialization for a `Vec<GE>` (e.g. the per-nonce
+/// public-key vector `R_j_vec`)
+pub mod vec_ge {
+    use super::*;
+    use serde::ser::SerializeSeq;
+
+    pub fn serialize<S>(points: &[GE], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(points.len()))?;
+        for point in points {
+            seq.serialize_element(&point.bytes_compressed_to_big_int().to_hex())?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<GE>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hexes: Vec<String> = serde::Deserialize::deserialize(deserializer)?;
+        hexes
+            .into_iter()
+            .map(|hex| {
+                let n = BigInt::from_hex(&hex);
+                ECPoint::from_bytes(&BigInt::to_vec(&n)).map_err(|_| Error::custom("invalid point"))
+            })
+            .collect()
+    }
+}
+
+/// fixed-width big-endian scalar serialization for a single `FE`
+pub mod fe {
+    use super::*;
+
+    pub fn serialize<S>(scalar: &FE, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&bigint_to_fixed_hex(&scalar.to_big_int(), FE_BYTE_LEN))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<FE, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hex = deserializer.deserialize_str(HexVisitor)?;
+        Ok(ECScalar::from(&BigInt::from_hex(&hex)))
+    }
+}
+
+/// variable-length big-endian hex serialization for a `BigInt`
+pub mod bigint {
+    use super::*;
+
+    pub fn serialize<S>(n: &BigInt, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&n.to_hex())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<BigInt, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hex = deserializer.deserialize_str(HexVisitor)?;
+        Ok(BigInt::from_hex(&hex))
+    }
+}
+
+/// variable-length big-endian hex serialization for a `Vec<BigInt>` (e.g.
+/// the MuSig2 `b_coefficients`)
+pub mod vec_bigint {
+    use super::*;
+    use serde::ser::SerializeSeq;
+
+    pub fn serialize<S>(values: &[BigInt], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+        for value in values {
+            seq.serialize_element(&value.to_hex())?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<BigInt>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hexes: Vec<String> = serde::Deserialize::deserialize(deserializer)?;
+        Ok(hexes.iter().map(|hex| BigInt::from_hex(hex)).collect())
+    }
+}
+
+/// fixed-width big-endian scalar serialization for an `Option<FE>` (the
+/// not-yet-filled-in `second_msg` slot of `Msg`)
+pub mod fe_opt {
+    use super::*;
+
+    pub fn serialize<S>(scalar: &Option<FE>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match scalar {
+            Some(scalar) => {
+                serializer.serialize_some(&bigint_to_fixed_hex(&scalar.to_big_int(), FE_BYTE_LEN))
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<FE>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hex: Option<String> = serde::Deserialize::deserialize(deserializer)?;
+        Ok(hex.map(|hex| ECScalar::from(&BigInt::from_hex(&hex))))
+    }
+}
+
+struct HexVisitor;
+
+impl<'de> Visitor<'de> for HexVisitor {
+    type Value = String;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a hex-encoded string")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<String, E>
+    where
+        E: Error,
+    {
+        Ok(v.to_owned())
+    }
+}
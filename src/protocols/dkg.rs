@@ -0,0 +1,259 @@
+//! trustless distributed key generation (DKG)
+//!
+//! A Pedersen/Feldman verifiable DKG: every participant deals a random
+//! degree-(t-1) polynomial, broadcasts Feldman commitments to its
+//! coefficients plus a proof of possession of the constant term, and
+//! privately sends every other participant its share. A recipient only
+//! accepts a share after verifying it against the dealer's commitments, so a
+//! cheating dealer is caught and blamed instead of silently corrupting the
+//! group key. Mirrors the commit/decommit pattern `aggsig`'s `EphemeralKey`
+//! uses for nonces.
+use curv::{BigInt, FE, GE};
+
+use curv::cryptographic_primitives::commitments::hash_commitment::HashCommitment;
+use curv::cryptographic_primitives::commitments::traits::*;
+use curv::cryptographic_primitives::proofs::sigma_dlog::DLogProof;
+use curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use curv::elliptic::curves::traits::*;
+
+use protocols::aggsig::musig_three_rounds::KeyPair;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Parameters {
+    pub threshold: usize,
+    pub share_count: usize,
+}
+
+#[derive(Debug)]
+pub enum DkgError {
+    /// the share received from `dealer_index` failed Feldman verification
+    InvalidShare { dealer_index: usize },
+    /// the proof of possession broadcast by `dealer_index` did not verify
+    InvalidProofOfPossession { dealer_index: usize },
+    /// a dealer's decommitment did not match its round-1 hash commitment
+    InvalidDecommitment { dealer_index: usize },
+}
+
+/// round 1: a dealer's hash-commitment to its constant-term commitment
+/// C_{i,0} = g^{a_{i,0}}, broadcast before any Feldman commitments are
+/// revealed
+#[derive(Clone, Debug)]
+pub struct DealerCommitment {
+    pub com: BigInt,
+}
+
+/// round 2: a dealer's decommitment, consisting of the full vector of
+/// Feldman commitments C_{i,0..t-1} to its polynomial's coefficients and a
+/// Schnorr proof of possession of a_{i,0}
+#[derive(Clone, Debug)]
+pub struct DealerDecommitment {
+    pub vss_scheme: VerifiableSS,
+    pub proof_of_possession: DLogProof,
+    pub blind_factor: BigInt,
+}
+
+/// a dealer's private contribution: its sampled polynomial together with
+/// the per-participant shares f_i(1)..f_i(n) derived from it
+pub struct Dealer {
+    params: Parameters,
+    vss_scheme: VerifiableSS,
+    secret_shares: Vec<FE>,
+    proof_of_possession: DLogProof,
+    blind_factor: BigInt,
+}
+
+impl Dealer {
+    /// samples a random degree-(t-1) polynomial f(x) = a_0 + a_1 x + ... and
+    /// Feldman-shares it across `params.share_count` participants
+    pub fn deal(params: &Parameters) -> (Dealer, DealerCommitment) {
+        let a_0: FE = ECScalar::new_random();
+        let (vss_scheme, secret_shares) =
+            VerifiableSS::share(params.threshold, params.share_count, &a_0);
+        let proof_of_possession = DLogProof::prove(&a_0);
+        let (com, blind_factor) =
+            HashCommitment::create_commitment(&vss_scheme.commitments[0].bytes_compressed_to_big_int());
+        (
+            Dealer {
+                params: *params,
+                vss_scheme,
+                secret_shares,
+                proof_of_possession,
+                blind_factor: blind_factor.clone(),
+            },
+            DealerCommitment { com },
+        )
+    }
+
+    pub fn decommit(&self) -> DealerDecommitment {
+        DealerDecommitment {
+            vss_scheme: self.vss_scheme.clone(),
+            proof_of_possession: self.proof_of_possession.clone(),
+            blind_factor: self.blind_factor.clone(),
+        }
+    }
+
+    /// the share this dealer privately sends to participant `party_index`
+    /// (1-indexed, matching the point labels `VerifiableSS` shares over)
+    pub fn share_for(&self, party_index: usize) -> FE {
+        assert!(
+            party_index >= 1 && party_index <= self.params.share_count,
+            "party_index out of range for this dealer's share_count"
+        );
+        self.secret_shares[party_index - 1]
+    }
+
+    pub fn group_commitment(&self) -> GE {
+        self.vss_scheme.commitments[0]
+    }
+}
+
+/// verifies dealer `dealer_index`'s round-2 decommitment against its round-1
+/// commitment and proof of possession; returns the dealer's constant-term
+/// commitment C_{i,0} on success
+pub fn verify_decommitment(
+    dealer_index: usize,
+    commitment: &DealerCommitment,
+    decommitment: &DealerDecommitment,
+) -> Result<GE, DkgError> {
+    let c_i0 = decommitment.vss_scheme.commitments[0];
+    let computed_com = HashCommitment::create_commitment_with_user_defined_randomness(
+        &c_i0.bytes_compressed_to_big_int(),
+        &decommitment.blind_factor,
+    );
+    if computed_com != commitment.com {
+        return Err(DkgError::InvalidDecommitment { dealer_index });
+    }
+    if DLogProof::verify(&decommitment.proof_of_possession).is_err()
+        || decommitment.proof_of_possession.pk != c_i0
+    {
+        return Err(DkgError::InvalidProofOfPossession { dealer_index });
+    }
+    Ok(c_i0)
+}
+
+/// verifies the share received from dealer `dealer_index` against that
+/// dealer's Feldman commitments: g^{f_i(j)} =?= prod_k C_{i,k}^{(j^k)}
+pub fn verify_share(
+    dealer_index: usize,
+    my_index: usize,
+    share: &FE,
+    decommitment: &DealerDecommitment,
+) -> Result<(), DkgError> {
+    decommitment
+        .vss_scheme
+        .validate_share(share, my_index)
+        .map_err(|_| DkgError::InvalidShare { dealer_index })
+}
+
+/// the final, locally-held output of a successful DKG run: the group public
+/// key and this participant's long-term signing share
+pub struct KeyGenOutput {
+    pub index: usize,
+    pub group_public_key: GE,
+    pub key_pair: KeyPair,
+}
+
+/// combines the group's constant-term commitments into the shared public
+/// key apk = sum_i C_{i,0}
+pub fn combine_group_public_key(dealer_commitments: &[GE]) -> GE {
+    let (first, rest) = dealer_commitments
+        .split_first()
+        .expect("at least one dealer is required");
+    rest.iter().fold(*first, |acc, c| acc.add_point(&c.get_element()))
+}
+
+/// once every dealt share has been verified with [`verify_share`], combine
+/// them into this participant's long-term signing share x_j = sum_i f_i(j)
+/// and pair it with the jointly generated group public key
+pub fn finalize(index: usize, group_public_key: GE, verified_shares: &[FE]) -> KeyGenOutput {
+    let x_j = verified_shares
+        .iter()
+        .fold(ECScalar::zero(), |acc: FE, share| acc + share);
+    let key_pair = KeyPair::create_from_private_key(&x_j.to_big_int());
+    KeyGenOutput {
+        index,
+        group_public_key,
+        key_pair,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use protocols::aggsig::musig_three_rounds::lagrange_coefficient;
+
+    #[test]
+    fn test_dkg_round_trip_reproduces_reconstructable_group_key() {
+        let params = Parameters {
+            threshold: 2,
+            share_count: 3,
+        };
+
+        // round 1+2: every participant deals, broadcasts its commitment,
+        // then decommits
+        let (dealers, commitments): (Vec<_>, Vec<_>) =
+            (0..params.share_count).map(|_| Dealer::deal(&params)).unzip();
+        let decommitments: Vec<_> = dealers.iter().map(|d| d.decommit()).collect();
+
+        for i in 0..params.share_count {
+            let c_i0 = verify_decommitment(i, &commitments[i], &decommitments[i]).unwrap();
+            assert_eq!(c_i0, dealers[i].group_commitment());
+        }
+
+        // round 3: each participant collects and verifies its share from
+        // every dealer, then finalizes its long-term signing share
+        let group_public_key =
+            combine_group_public_key(&dealers.iter().map(|d| d.group_commitment()).collect::<Vec<_>>());
+
+        let mut key_pairs = Vec::new();
+        for my_index in 1..=params.share_count {
+            let verified_shares: Vec<FE> = dealers
+                .iter()
+                .enumerate()
+                .map(|(dealer_index, dealer)| {
+                    let share = dealer.share_for(my_index);
+                    verify_share(dealer_index, my_index, &share, &decommitments[dealer_index])
+                        .unwrap();
+                    share
+                })
+                .collect();
+            let output = finalize(my_index, group_public_key, &verified_shares);
+            assert_eq!(output.group_public_key, group_public_key);
+            key_pairs.push((my_index, output.key_pair));
+        }
+
+        // reconstruct the group public key from any `threshold` signing
+        // shares' verification keys via Lagrange interpolation at x=0:
+        // sum_j lambda_j * g^{x_j} =?= g^{f(0)}
+        let quorum: Vec<usize> = key_pairs
+            .iter()
+            .take(params.threshold)
+            .map(|(i, _)| *i)
+            .collect();
+        let (first_index, first_key_pair) = &key_pairs[0];
+        let first_lambda: FE = ECScalar::from(&lagrange_coefficient(*first_index, &quorum));
+        let reconstructed = key_pairs.iter().skip(1).take(params.threshold - 1).fold(
+            first_key_pair.public_key * first_lambda,
+            |acc, (index, key_pair)| {
+                let lambda: FE = ECScalar::from(&lagrange_coefficient(*index, &quorum));
+                acc.add_point(&(key_pair.public_key * lambda).get_element())
+            },
+        );
+        assert_eq!(reconstructed, group_public_key);
+    }
+
+    #[test]
+    fn test_invalid_share_is_caught_not_trusted() {
+        let params = Parameters {
+            threshold: 2,
+            share_count: 3,
+        };
+        let (dealer, commitment) = Dealer::deal(&params);
+        let decommitment = dealer.decommit();
+        verify_decommitment(0, &commitment, &decommitment).unwrap();
+
+        let real_share = dealer.share_for(1);
+        let forged_share = real_share + ECScalar::from(&BigInt::from(1));
+        assert!(verify_share(0, 1, &forged_share, &decommitment).is_err());
+    }
+}
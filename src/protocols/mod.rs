@@ -0,0 +1,2 @@
+pub mod aggsig;
+pub mod dkg;